@@ -0,0 +1,6 @@
+fn main() {
+    let attributes = tauri_build::Attributes::new()
+        .android_path("android")
+        .ios_path("ios");
+    tauri_build::try_build(attributes).expect("failed to run tauri-build");
+}
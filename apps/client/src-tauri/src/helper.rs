@@ -0,0 +1,204 @@
+//! External helper launcher.
+//!
+//! Lets the thin client hand DWN CLI actions or signing operations off to
+//! an external program or a terminal session instead of reimplementing
+//! them natively.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::{AppHandle, Manager, Runtime};
+
+const TERMINAL_OVERRIDE_FILE: &str = "terminal-override.json";
+
+/// Structured so the frontend can tell "nothing found, prompt the user to
+/// configure one" apart from "found it, but it wouldn't launch".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HelperError {
+    NotFound { program: String },
+    SpawnFailed { program: String, message: String },
+}
+
+impl std::fmt::Display for HelperError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HelperError::NotFound { program } => write!(f, "could not find '{program}' on PATH"),
+            HelperError::SpawnFailed { program, message } => {
+                write!(f, "failed to launch '{program}': {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HelperError {}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct TerminalOverride {
+    program: Option<String>,
+}
+
+fn override_path<R: Runtime>(app: &AppHandle<R>) -> Option<PathBuf> {
+    let dir = app.path().app_data_dir().ok()?;
+    fs::create_dir_all(&dir).ok()?;
+    Some(dir.join(TERMINAL_OVERRIDE_FILE))
+}
+
+fn load_override<R: Runtime>(app: &AppHandle<R>) -> Option<String> {
+    let bytes = fs::read(override_path(app)?).ok()?;
+    serde_json::from_slice::<TerminalOverride>(&bytes)
+        .ok()?
+        .program
+}
+
+/// Set (or clear, with `None`) the user's preferred terminal emulator.
+#[tauri::command]
+pub async fn set_terminal_override<R: Runtime>(
+    app: AppHandle<R>,
+    program: Option<String>,
+) -> Result<(), String> {
+    let path = override_path(&app).ok_or("could not resolve the app data directory")?;
+    let bytes =
+        serde_json::to_vec(&TerminalOverride { program }).map_err(|e| e.to_string())?;
+    fs::write(path, bytes).map_err(|e| e.to_string())
+}
+
+/// Platform default(s) tried before the generic cross-platform candidates.
+fn candidate_terminals() -> Vec<&'static str> {
+    let mut candidates = Vec::new();
+    #[cfg(target_os = "macos")]
+    candidates.push("osascript");
+    #[cfg(target_os = "linux")]
+    candidates.push("x-terminal-emulator");
+    candidates.extend(["wezterm", "alacritty", "gnome-terminal", "konsole"]);
+    #[cfg(target_os = "windows")]
+    candidates.extend(["wt", "cmd"]);
+    candidates
+}
+
+fn validate_binary(program: &str, path: &Path) -> Result<(), HelperError> {
+    if path.is_file() {
+        Ok(())
+    } else {
+        Err(HelperError::NotFound {
+            program: program.to_string(),
+        })
+    }
+}
+
+fn resolve(program: &str) -> Result<PathBuf, HelperError> {
+    let resolved = which::which(program).map_err(|_| HelperError::NotFound {
+        program: program.to_string(),
+    })?;
+    validate_binary(program, &resolved)?;
+    Ok(resolved)
+}
+
+/// Resolve `program` on `PATH` and spawn it with `args`.
+#[tauri::command]
+pub async fn launch_helper(program: String, args: Vec<String>) -> Result<(), HelperError> {
+    let resolved = resolve(&program)?;
+    Command::new(resolved)
+        .args(&args)
+        .spawn()
+        .map_err(|e| HelperError::SpawnFailed {
+            program,
+            message: e.to_string(),
+        })?;
+    Ok(())
+}
+
+/// Write `command` to a throwaway shell script and return its path, so the
+/// AppleScript branch of [`spawn_in_terminal`] never has to embed untrusted
+/// content inside a quoted AppleScript string literal.
+fn write_temp_script(command: &str) -> Result<PathBuf, HelperError> {
+    let spawn_failed = |message: String| HelperError::SpawnFailed {
+        program: "osascript".to_string(),
+        message,
+    };
+
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|e| spawn_failed(e.to_string()))?
+        .as_nanos();
+    let path = std::env::temp_dir().join(format!("dex-helper-{}-{nanos}.sh", std::process::id()));
+
+    // `$0` is the script's own path; deleting it after running `command`
+    // means a terminal hand-off doesn't leave the (potentially sensitive)
+    // command text sitting in the temp dir indefinitely.
+    fs::write(&path, format!("#!/bin/sh\n{command}\nrm -- \"$0\"\n"))
+        .map_err(|e| spawn_failed(e.to_string()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&path)
+            .map_err(|e| spawn_failed(e.to_string()))?
+            .permissions();
+        perms.set_mode(0o700);
+        fs::set_permissions(&path, perms).map_err(|e| spawn_failed(e.to_string()))?;
+    }
+
+    Ok(path)
+}
+
+fn spawn_in_terminal(program: &str, command: &str) -> Result<(), HelperError> {
+    let resolved = resolve(program)?;
+    let result = match program {
+        "wezterm" => Command::new(&resolved)
+            .args(["start", "--", "sh", "-c", command])
+            .spawn(),
+        "alacritty" | "konsole" => Command::new(&resolved).args(["-e", "sh", "-c", command]).spawn(),
+        "gnome-terminal" | "x-terminal-emulator" => {
+            Command::new(&resolved).args(["--", "sh", "-c", command]).spawn()
+        }
+        "wt" => Command::new(&resolved).args(["sh", "-c", command]).spawn(),
+        "cmd" => Command::new(&resolved).args(["/C", command]).spawn(),
+        "osascript" => {
+            // The script path is ours (a temp-dir path we just generated),
+            // never the caller's `command`, so it's safe to embed directly
+            // in the AppleScript string literal below.
+            let script_path = write_temp_script(command)?;
+            Command::new(&resolved)
+                .args([
+                    "-e",
+                    &format!(
+                        "tell application \"Terminal\" to do script \"sh {}\"",
+                        script_path.display()
+                    ),
+                ])
+                .spawn()
+        }
+        _ => Command::new(&resolved).arg(command).spawn(),
+    };
+
+    result
+        .map(|_| ())
+        .map_err(|e| HelperError::SpawnFailed {
+            program: program.to_string(),
+            message: e.to_string(),
+        })
+}
+
+/// Open `command` in the user's configured terminal, falling back through
+/// [`candidate_terminals`] when no override is set.
+#[tauri::command]
+pub async fn open_in_terminal<R: Runtime>(
+    app: AppHandle<R>,
+    command: String,
+) -> Result<(), HelperError> {
+    if let Some(program) = load_override(&app) {
+        return spawn_in_terminal(&program, &command);
+    }
+
+    for candidate in candidate_terminals() {
+        if resolve(candidate).is_ok() {
+            return spawn_in_terminal(candidate, &command);
+        }
+    }
+
+    Err(HelperError::NotFound {
+        program: "a terminal emulator".to_string(),
+    })
+}
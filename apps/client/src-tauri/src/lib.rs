@@ -1,82 +1,49 @@
 //! Dex Thin Client - Tauri backend
 //!
 //! Provides native functionality for the thin client:
-//! - Secure credential storage (keychain)
+//! - Secure credential storage (vault backed by keychain or encrypted file)
 //! - System tray integration
 //! - Native notifications
+//! - Opt-in crash/error reporting
+//! - Auto-updates, surfaced through the tray
+//! - Launching external helpers (editors, terminals, CLI tools)
+
+mod helper;
+#[cfg(any(target_os = "android", target_os = "ios"))]
+mod mobile_secure_storage;
+mod shortcuts;
+mod telemetry;
+mod updater;
+mod vault;
 
 use tauri::{
     menu::{Menu, MenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager, Runtime,
+    Emitter, Manager, Runtime,
 };
 
-#[cfg(not(any(target_os = "android", target_os = "ios")))]
-use keyring::Entry;
-
-/// Save auth key to system keychain
-#[tauri::command]
-async fn save_auth_key(key: String) -> Result<(), String> {
-    #[cfg(not(any(target_os = "android", target_os = "ios")))]
-    {
-        let entry = Entry::new("dex", "auth-key").map_err(|e| e.to_string())?;
-        entry.set_password(&key).map_err(|e| e.to_string())?;
-        Ok(())
-    }
-
-    #[cfg(any(target_os = "android", target_os = "ios"))]
-    {
-        // On mobile, we'll use Tauri's storage or a secure storage plugin
-        // For now, just succeed (we can implement proper secure storage later)
-        let _ = key;
-        Ok(())
-    }
-}
-
-/// Get auth key from system keychain
-#[tauri::command]
-async fn get_auth_key() -> Result<Option<String>, String> {
-    #[cfg(not(any(target_os = "android", target_os = "ios")))]
-    {
-        let entry = Entry::new("dex", "auth-key").map_err(|e| e.to_string())?;
-        match entry.get_password() {
-            Ok(key) => Ok(Some(key)),
-            Err(keyring::Error::NoEntry) => Ok(None),
-            Err(e) => Err(e.to_string()),
-        }
-    }
-
-    #[cfg(any(target_os = "android", target_os = "ios"))]
-    {
-        // On mobile, return None for now
-        Ok(None)
-    }
-}
-
-/// Delete auth key from system keychain
-#[tauri::command]
-async fn delete_auth_key() -> Result<(), String> {
-    #[cfg(not(any(target_os = "android", target_os = "ios")))]
-    {
-        let entry = Entry::new("dex", "auth-key").map_err(|e| e.to_string())?;
-        match entry.delete_credential() {
-            Ok(()) => Ok(()),
-            Err(keyring::Error::NoEntry) => Ok(()), // Already deleted
-            Err(e) => Err(e.to_string()),
-        }
-    }
+/// The app's bundle identifier, matching the `identifier` in
+/// `tauri.conf.json`. [`telemetry::init`] runs before the Tauri builder
+/// exists (so there's no `AppHandle` to ask for the app-data dir yet) and
+/// has to derive the same platform data directory Tauri itself would.
+pub(crate) const APP_IDENTIFIER: &str = "com.dex.app";
 
-    #[cfg(any(target_os = "android", target_os = "ios"))]
-    {
-        Ok(())
-    }
-}
+use helper::{launch_helper, open_in_terminal, set_terminal_override};
+use shortcuts::{clear_shortcut, get_shortcuts, set_shortcut};
+use telemetry::{
+    get_minidump_reporting_enabled, get_telemetry_enabled, set_minidump_reporting_enabled,
+    set_telemetry_enabled,
+};
+use updater::{check_for_update, install_update, set_auto_check_interval, UpdateState};
+use vault::{vault_backend_for, vault_delete, vault_get, vault_list_accounts, vault_set};
 
-/// Set up the system tray
-fn setup_tray<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::error::Error>> {
+/// Set up the system tray. Returns the (initially disabled) "Update
+/// available" menu item so the updater can flip it on from the background.
+fn setup_tray<R: Runtime>(app: &tauri::App<R>) -> Result<MenuItem<R>, Box<dyn std::error::Error>> {
+    let update = MenuItem::with_id(app, "update", "No updates available", false, None::<&str>)?;
     let quit = MenuItem::with_id(app, "quit", "Quit Dex", true, None::<&str>)?;
     let show = MenuItem::with_id(app, "show", "Show Dex", true, None::<&str>)?;
-    let menu = Menu::with_items(app, &[&show, &quit])?;
+    let menu = Menu::with_items(app, &[&show, &update, &quit])?;
 
     let _tray = TrayIconBuilder::new()
         .icon(app.default_window_icon().unwrap().clone())
@@ -92,6 +59,9 @@ fn setup_tray<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::error:
                     let _ = window.set_focus();
                 }
             }
+            "update" => {
+                let _ = app.emit("tray-install-update", ());
+            }
             _ => {}
         })
         .on_tray_icon_event(|tray, event| {
@@ -110,25 +80,106 @@ fn setup_tray<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::error:
         })
         .build(app)?;
 
-    Ok(())
+    Ok(update)
+}
+
+/// Pull a `dex://` deep-link argument out of a second instance's argv, if
+/// one was passed (e.g. the OS activated the protocol handler).
+fn extract_deep_link(args: &[String]) -> Option<String> {
+    args.iter().find(|arg| arg.starts_with("dex://")).cloned()
+}
+
+/// Registers single-instance enforcement on desktop. A second launch
+/// focuses the running window and forwards its argv instead of starting a
+/// competing process (and fighting over the keychain entry).
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn with_single_instance<R: Runtime>(builder: tauri::Builder<R>) -> tauri::Builder<R> {
+    builder.plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+        if let Some(url) = extract_deep_link(&args) {
+            let _ = app.emit("deep-link", url);
+        }
+    }))
+}
+
+#[cfg(any(target_os = "android", target_os = "ios"))]
+fn with_single_instance<R: Runtime>(builder: tauri::Builder<R>) -> tauri::Builder<R> {
+    builder
+}
+
+/// The updater plugin only targets desktop.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn with_updater<R: Runtime>(builder: tauri::Builder<R>) -> tauri::Builder<R> {
+    builder.plugin(tauri_plugin_updater::Builder::new().build())
+}
+
+#[cfg(any(target_os = "android", target_os = "ios"))]
+fn with_updater<R: Runtime>(builder: tauri::Builder<R>) -> tauri::Builder<R> {
+    builder
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
+    // Initialized before anything else so startup crashes are captured too.
+    // Returns None (and sends nothing) until the user has opted in.
+    let _telemetry_guard = telemetry::init();
+
+    // Single-instance must be registered first so it wins the race against
+    // the rest of app startup.
+    let builder = with_updater(with_single_instance(tauri::Builder::default()));
+    builder
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .invoke_handler(tauri::generate_handler![
-            save_auth_key,
-            get_auth_key,
-            delete_auth_key,
+            vault_set,
+            vault_get,
+            vault_delete,
+            vault_list_accounts,
+            vault_backend_for,
+            set_shortcut,
+            clear_shortcut,
+            get_shortcuts,
+            set_telemetry_enabled,
+            get_telemetry_enabled,
+            set_minidump_reporting_enabled,
+            get_minidump_reporting_enabled,
+            check_for_update,
+            install_update,
+            set_auto_check_interval,
+            launch_helper,
+            open_in_terminal,
+            set_terminal_override,
         ])
         .setup(|app| {
             // Set up tray on desktop only
             #[cfg(not(any(target_os = "android", target_os = "ios")))]
             {
-                setup_tray(app)?;
+                let update_item = setup_tray(app)?;
+                app.manage(UpdateState::new(update_item));
+                updater::spawn_background_checks(app.handle());
+            }
+
+            // Register platform secure storage on mobile so the vault can
+            // use it in place of the (nonexistent) OS keychain.
+            #[cfg(any(target_os = "android", target_os = "ios"))]
+            {
+                app.handle().plugin(
+                    tauri::plugin::Builder::new("secure-storage")
+                        .setup(|app, api| {
+                            let storage = mobile_secure_storage::init(app, api)?;
+                            app.manage(storage);
+                            Ok(())
+                        })
+                        .build(),
+                )?;
             }
+
+            shortcuts::register_all(app.handle())?;
+
             Ok(())
         })
         .run(tauri::generate_context!())
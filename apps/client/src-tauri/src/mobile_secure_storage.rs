@@ -0,0 +1,92 @@
+//! Bridge to the platform secure-storage plugin used by [`crate::vault`] on
+//! mobile, where there is no desktop-style OS keychain to talk to directly.
+//!
+//! On iOS this forwards to Keychain Services (`kSecClassGenericPassword`,
+//! scoped to an access group so the extension/widget targets can share it),
+//! implemented in `ios/Sources/SecureStoragePlugin`. On Android it forwards
+//! to an Android Keystore-wrapped AES key that encrypts the secret before it
+//! lands in `EncryptedSharedPreferences`, implemented in
+//! `android/src/main/java/com/dex/securestorage`. This module only defines
+//! the Rust-side plugin handle and the request/response shapes sent across
+//! the bridge; `build.rs` points `tauri-build` at both native source trees.
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    plugin::{PluginApi, PluginHandle},
+    AppHandle, Runtime,
+};
+
+// Must match the Android library's package exactly (see
+// `android/src/main/java/com/dex/securestorage/SecureStoragePlugin.kt` and
+// its `build.gradle.kts` `namespace`) — Tauri uses this to locate and
+// instantiate the plugin class on the Java side.
+#[cfg(target_os = "android")]
+const PLUGIN_IDENTIFIER: &str = "com.dex.securestorage";
+
+#[derive(Debug, Clone, Serialize)]
+struct SetRequest<'a> {
+    service: &'a str,
+    account: &'a str,
+    secret: &'a str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GetOrDeleteRequest<'a> {
+    service: &'a str,
+    account: &'a str,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GetResponse {
+    secret: Option<String>,
+}
+
+pub struct SecureStorage<R: Runtime>(PluginHandle<R>);
+
+impl<R: Runtime> SecureStorage<R> {
+    pub fn set(&self, service: &str, account: &str, secret: &str) -> Result<(), String> {
+        self.0
+            .run_mobile_plugin::<()>("set", SetRequest { service, account, secret })
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn get(&self, service: &str, account: &str) -> Result<Option<String>, String> {
+        self.0
+            .run_mobile_plugin::<GetResponse>("get", GetOrDeleteRequest { service, account })
+            .map(|res| res.secret)
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn delete(&self, service: &str, account: &str) -> Result<(), String> {
+        self.0
+            .run_mobile_plugin::<()>("delete", GetOrDeleteRequest { service, account })
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(target_os = "ios")]
+pub fn init<R: Runtime>(
+    _app: &AppHandle<R>,
+    api: PluginApi<R, ()>,
+) -> Result<SecureStorage<R>, String> {
+    let handle = api
+        .register_ios_plugin(init_plugin_ios)
+        .map_err(|e| e.to_string())?;
+    Ok(SecureStorage(handle))
+}
+
+#[cfg(target_os = "android")]
+pub fn init<R: Runtime>(
+    _app: &AppHandle<R>,
+    api: PluginApi<R, ()>,
+) -> Result<SecureStorage<R>, String> {
+    let handle = api
+        .register_android_plugin(PLUGIN_IDENTIFIER, "SecureStoragePlugin")
+        .map_err(|e| e.to_string())?;
+    Ok(SecureStorage(handle))
+}
+
+#[cfg(target_os = "ios")]
+extern "C" {
+    fn init_plugin_ios(webview: *mut std::ffi::c_void) -> *mut std::ffi::c_void;
+}
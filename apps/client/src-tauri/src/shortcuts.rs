@@ -0,0 +1,194 @@
+//! Global shortcut subsystem.
+//!
+//! Lets the user toggle the main window or trigger a quick action from
+//! anywhere, even when Dex isn't focused. Bindings are user-editable from
+//! the frontend, persisted in app-data, and re-registered every time the
+//! app starts.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+const SHORTCUTS_FILE: &str = "shortcuts.json";
+
+/// Built-in action that shows+focuses the main window, or hides it if
+/// already focused.
+pub const ACTION_TOGGLE_WINDOW: &str = "toggle-window";
+/// Built-in action that just emits an event for the frontend to react to.
+pub const ACTION_QUICK_ACTION: &str = "quick-action";
+
+/// A single configured hotkey. `enabled` lets the user disable a binding
+/// without losing the accelerator they chose.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortcutBinding {
+    pub accelerator: String,
+    pub enabled: bool,
+}
+
+type ShortcutMap = HashMap<String, ShortcutBinding>;
+
+fn default_bindings() -> ShortcutMap {
+    HashMap::from([
+        (
+            ACTION_TOGGLE_WINDOW.to_string(),
+            ShortcutBinding {
+                accelerator: "CmdOrCtrl+Shift+D".to_string(),
+                enabled: true,
+            },
+        ),
+        (
+            ACTION_QUICK_ACTION.to_string(),
+            ShortcutBinding {
+                accelerator: "CmdOrCtrl+Shift+N".to_string(),
+                enabled: true,
+            },
+        ),
+    ])
+}
+
+fn shortcuts_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(SHORTCUTS_FILE))
+}
+
+fn load_bindings<R: Runtime>(app: &AppHandle<R>) -> Result<ShortcutMap, String> {
+    let path = shortcuts_path(app)?;
+    match fs::read(&path) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| e.to_string()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(default_bindings()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn save_bindings<R: Runtime>(app: &AppHandle<R>, bindings: &ShortcutMap) -> Result<(), String> {
+    let path = shortcuts_path(app)?;
+    let bytes = serde_json::to_vec_pretty(bindings).map_err(|e| e.to_string())?;
+    fs::write(path, bytes).map_err(|e| e.to_string())
+}
+
+fn toggle_main_window<R: Runtime>(app: &AppHandle<R>) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let is_focused = window.is_focused().unwrap_or(false);
+    if is_focused {
+        let _ = window.hide();
+    } else {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn dispatch<R: Runtime>(app: &AppHandle<R>, action: &str) {
+    match action {
+        ACTION_TOGGLE_WINDOW => toggle_main_window(app),
+        ACTION_QUICK_ACTION => {
+            let _ = app.emit("quick-action", ());
+        }
+        _ => {}
+    }
+}
+
+fn register<R: Runtime>(app: &AppHandle<R>, action: &str, accelerator: &str) -> Result<(), String> {
+    let shortcut: Shortcut = accelerator.parse().map_err(|e| format!("{e}"))?;
+    let action = action.to_string();
+    app.global_shortcut()
+        .on_shortcut(shortcut, move |app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                dispatch(app, &action);
+            }
+        })
+        .map_err(|e| e.to_string())
+}
+
+fn unregister<R: Runtime>(app: &AppHandle<R>, accelerator: &str) {
+    if let Ok(shortcut) = accelerator.parse::<Shortcut>() {
+        let _ = app.global_shortcut().unregister(shortcut);
+    }
+}
+
+/// Register every enabled binding. Called once at startup after loading the
+/// persisted (or default) bindings. A single accelerator being unavailable
+/// (e.g. already claimed by another running app) is expected, not fatal —
+/// skip it and keep going rather than failing the whole app launch over
+/// one hotkey conflict.
+pub fn register_all<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    let bindings = load_bindings(app)?;
+    for (action, binding) in bindings.iter() {
+        if !binding.enabled {
+            continue;
+        }
+        if let Err(e) = register(app, action, &binding.accelerator) {
+            eprintln!(
+                "dex: could not register shortcut '{}' for '{action}': {e}",
+                binding.accelerator
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Set (and immediately register) the accelerator for `action`, replacing
+/// whatever was previously bound to it.
+#[tauri::command]
+pub async fn set_shortcut<R: Runtime>(
+    app: AppHandle<R>,
+    action: String,
+    accelerator: String,
+) -> Result<(), String> {
+    let mut bindings = load_bindings(&app)?;
+    let existing = bindings.get(&action).cloned();
+
+    if let Some(existing) = &existing {
+        if existing.enabled && existing.accelerator == accelerator {
+            // Already bound to this accelerator; nothing to do.
+            return Ok(());
+        }
+    }
+
+    // Register the new accelerator before touching the old one, so a bad
+    // accelerator (invalid, or already claimed by another app) leaves the
+    // previously-working binding untouched instead of registered-nowhere
+    // until the next restart.
+    register(&app, &action, &accelerator)?;
+
+    if let Some(existing) = &existing {
+        if existing.enabled {
+            unregister(&app, &existing.accelerator);
+        }
+    }
+
+    bindings.insert(
+        action,
+        ShortcutBinding {
+            accelerator,
+            enabled: true,
+        },
+    );
+    save_bindings(&app, &bindings)
+}
+
+/// Disable `action`'s binding without forgetting the configured accelerator.
+#[tauri::command]
+pub async fn clear_shortcut<R: Runtime>(app: AppHandle<R>, action: String) -> Result<(), String> {
+    let mut bindings = load_bindings(&app)?;
+    if let Some(binding) = bindings.get_mut(&action) {
+        if binding.enabled {
+            unregister(&app, &binding.accelerator);
+        }
+        binding.enabled = false;
+    }
+    save_bindings(&app, &bindings)
+}
+
+/// The full set of configured bindings, enabled or not, for the settings UI.
+#[tauri::command]
+pub async fn get_shortcuts<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<HashMap<String, ShortcutBinding>, String> {
+    load_bindings(&app)
+}
@@ -0,0 +1,198 @@
+//! Opt-in crash and error reporting.
+//!
+//! Nothing is ever sent until the user explicitly flips this on: consent is
+//! persisted to disk and [`init`] only starts the Sentry client when it
+//! reads back `enabled`. This module has to run before the Tauri builder so
+//! native crashes during startup are captured too, which means it can't
+//! rely on an `AppHandle` to find its storage location the way the rest of
+//! the app-data-backed subsystems do.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, Runtime};
+
+const TELEMETRY_FILE: &str = "telemetry.json";
+
+/// Keys scrubbed outright from structured event data (`extra`, breadcrumb
+/// `data`): the DWN auth key and anything else named like a secret.
+const SCRUBBED_KEYS: &[&str] = &[
+    "auth_key",
+    "authKey",
+    "secret",
+    "password",
+    "passphrase",
+    "token",
+];
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+struct TelemetryConfig {
+    enabled: bool,
+    /// Separate, off-by-default opt-in: native crash minidumps are raw
+    /// process memory and can contain a live auth key, relay token, or
+    /// vault passphrase in plaintext. `scrub_event` only rewrites the
+    /// structured event, not attachment bytes, so minidumps must not ride
+    /// along just because the user enabled telemetry in general.
+    #[serde(default)]
+    minidump_enabled: bool,
+}
+
+/// Resolves the same directory `AppHandle::path().app_data_dir()` would,
+/// without needing a running app. Only used by [`init`], which has to run
+/// before the Tauri builder exists; the commands below take an `AppHandle`
+/// and use the real resolver like every other module.
+fn bootstrap_telemetry_path() -> Option<PathBuf> {
+    let dir = dirs::data_dir()?.join(crate::APP_IDENTIFIER);
+    fs::create_dir_all(&dir).ok()?;
+    Some(dir.join(TELEMETRY_FILE))
+}
+
+fn telemetry_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(TELEMETRY_FILE))
+}
+
+fn load_config_from(path: Option<PathBuf>) -> TelemetryConfig {
+    path.and_then(|path| fs::read(path).ok())
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+/// A value that reads like an opaque secret rather than prose: a JWT
+/// (`header.payload.signature`) or a long run of token-safe characters,
+/// the shape a DWN auth key or relay token would take.
+fn looks_like_secret(word: &str) -> bool {
+    let is_token_char =
+        |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+' | '/' | '=');
+    let jwt_like = word.matches('.').count() == 2 && word.len() >= 20;
+    let opaque_blob = word.len() >= 24 && word.chars().all(is_token_char);
+    jwt_like || opaque_blob
+}
+
+fn redact_secret_like(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| if looks_like_secret(word) { "[redacted]" } else { word })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn scrub_map(map: &mut sentry::protocol::Map<String, serde_json::Value>) {
+    for (key, value) in map.iter_mut() {
+        if SCRUBBED_KEYS.iter().any(|scrubbed| key.eq_ignore_ascii_case(scrubbed)) {
+            *value = serde_json::Value::String("[redacted]".to_string());
+        } else if let serde_json::Value::String(s) = value {
+            *s = redact_secret_like(s);
+        }
+    }
+}
+
+fn scrub_event(mut event: sentry::protocol::Event<'static>) -> sentry::protocol::Event<'static> {
+    scrub_map(&mut event.extra);
+
+    // `sentry_tracing` puts a captured `tracing::error!` call's formatted
+    // message here, which is the primary path this subsystem exists to
+    // cover, so it needs the same scrubbing as exception values.
+    if let Some(message) = &mut event.message {
+        *message = redact_secret_like(message);
+    }
+
+    for exception in &mut event.exception.values {
+        if let Some(value) = &mut exception.value {
+            *value = redact_secret_like(value);
+        }
+    }
+
+    // Breadcrumbs are where the `sentry_tracing` layer lands logged field
+    // values, so they're the most likely place an actual secret (not just
+    // a field named like one) ends up.
+    for breadcrumb in &mut event.breadcrumbs.values {
+        if let Some(message) = &mut breadcrumb.message {
+            *message = redact_secret_like(message);
+        }
+        scrub_map(&mut breadcrumb.data);
+    }
+
+    event
+}
+
+/// Starts the Sentry client (native crash minidumps plus a `tracing` layer
+/// for captured errors) if the user has already opted in. Returns a guard
+/// that must be held for the lifetime of the process; dropping it flushes
+/// and shuts the client down. Returns `None` when telemetry is disabled, so
+/// callers should hold the `Option` rather than unwrapping it.
+pub fn init() -> Option<sentry::ClientInitGuard> {
+    let config = load_config_from(bootstrap_telemetry_path());
+    if !config.enabled {
+        return None;
+    }
+
+    let dsn = option_env!("DEX_SENTRY_DSN").unwrap_or("");
+    if dsn.is_empty() {
+        return None;
+    }
+
+    let guard = sentry::init((
+        dsn,
+        sentry::ClientOptions {
+            release: sentry::release_name!(),
+            attach_stacktrace: true,
+            before_send: Some(std::sync::Arc::new(|event| Some(scrub_event(event)))),
+            ..Default::default()
+        },
+    ));
+
+    // Minidumps are raw memory, not structured events, so `scrub_event`
+    // can't touch them — only attach them under their own explicit opt-in.
+    if config.minidump_enabled {
+        let _ = sentry::integrations::minidump::init(&guard);
+    }
+
+    use tracing_subscriber::layer::SubscriberExt;
+    let subscriber = tracing_subscriber::registry().with(sentry_tracing::layer());
+    let _ = tracing::subscriber::set_global_default(subscriber);
+
+    Some(guard)
+}
+
+fn write_config<R: Runtime>(app: &AppHandle<R>, config: TelemetryConfig) -> Result<(), String> {
+    let path = telemetry_path(app)?;
+    let bytes = serde_json::to_vec(&config).map_err(|e| e.to_string())?;
+    fs::write(path, bytes).map_err(|e| e.to_string())
+}
+
+/// Enable or disable telemetry. Takes effect on the next launch, since the
+/// client is wired up once at process start.
+#[tauri::command]
+pub async fn set_telemetry_enabled<R: Runtime>(
+    app: AppHandle<R>,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut config = load_config_from(telemetry_path(&app).ok());
+    config.enabled = enabled;
+    write_config(&app, config)
+}
+
+/// Whether the user has opted in to crash/error reporting.
+#[tauri::command]
+pub async fn get_telemetry_enabled<R: Runtime>(app: AppHandle<R>) -> Result<bool, String> {
+    Ok(load_config_from(telemetry_path(&app).ok()).enabled)
+}
+
+/// Enable or disable attaching native crash minidumps, separately from
+/// general telemetry consent — see the note on `TelemetryConfig`.
+#[tauri::command]
+pub async fn set_minidump_reporting_enabled<R: Runtime>(
+    app: AppHandle<R>,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut config = load_config_from(telemetry_path(&app).ok());
+    config.minidump_enabled = enabled;
+    write_config(&app, config)
+}
+
+/// Whether the user has separately opted in to minidump attachments.
+#[tauri::command]
+pub async fn get_minidump_reporting_enabled<R: Runtime>(app: AppHandle<R>) -> Result<bool, String> {
+    Ok(load_config_from(telemetry_path(&app).ok()).minidump_enabled)
+}
@@ -0,0 +1,210 @@
+//! Auto-updater with tray-driven update lifecycle.
+//!
+//! Checks run on a background timer (rate-limited by a user-controlled
+//! interval persisted in app-data) and on demand from the frontend. When an
+//! update is found we flip on the tray's "Update available" item and fire a
+//! native notification; installing emits download progress events so the
+//! frontend can show a progress bar.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::menu::MenuItem;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_updater::UpdaterExt;
+
+const STATE_FILE: &str = "update-check.json";
+const DEFAULT_CHECK_INTERVAL_SECS: u64 = 6 * 60 * 60;
+/// Floor for the user-configurable interval: the background loop in
+/// `spawn_background_checks` sleeps for whatever is persisted here, so an
+/// interval of 0 (or anything tiny) would hammer the update server.
+const MIN_CHECK_INTERVAL_SECS: u64 = 5 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UpdateCheckState {
+    last_checked_unix: Option<u64>,
+    auto_check_interval_secs: u64,
+}
+
+impl Default for UpdateCheckState {
+    fn default() -> Self {
+        Self {
+            last_checked_unix: None,
+            auto_check_interval_secs: DEFAULT_CHECK_INTERVAL_SECS,
+        }
+    }
+}
+
+/// Version/notes for an update the frontend can offer to install.
+#[derive(Debug, Clone, Serialize)]
+pub struct AvailableUpdate {
+    pub version: String,
+    pub notes: Option<String>,
+}
+
+/// The pending update returned by the last successful check, held so
+/// `install_update` doesn't have to re-check before installing. The tray's
+/// "Update available" menu item lives alongside it so a background check
+/// can flip it on without the frontend being involved.
+pub struct UpdateState<R: Runtime> {
+    pending: Mutex<Option<tauri_plugin_updater::Update>>,
+    tray_item: MenuItem<R>,
+}
+
+impl<R: Runtime> UpdateState<R> {
+    pub fn new(tray_item: MenuItem<R>) -> Self {
+        Self {
+            pending: Mutex::new(None),
+            tray_item,
+        }
+    }
+}
+
+fn state_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(STATE_FILE))
+}
+
+fn load_state<R: Runtime>(app: &AppHandle<R>) -> Result<UpdateCheckState, String> {
+    let path = state_path(app)?;
+    match fs::read(&path) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| e.to_string()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(UpdateCheckState::default()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn save_state<R: Runtime>(app: &AppHandle<R>, state: &UpdateCheckState) -> Result<(), String> {
+    let bytes = serde_json::to_vec(state).map_err(|e| e.to_string())?;
+    fs::write(state_path(app)?, bytes).map_err(|e| e.to_string())
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Check for an update, recording the attempt regardless of outcome. On a
+/// hit, enables the tray's "Update available" item and fires a native
+/// notification.
+#[tauri::command]
+pub async fn check_for_update<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Option<AvailableUpdate>, String> {
+    let updater = app.updater().map_err(|e| e.to_string())?;
+    let found = updater.check().await.map_err(|e| e.to_string())?;
+
+    let mut state = load_state(&app)?;
+    state.last_checked_unix = Some(now_unix());
+    save_state(&app, &state)?;
+
+    let Some(update) = found else {
+        return Ok(None);
+    };
+
+    let info = AvailableUpdate {
+        version: update.version.clone(),
+        notes: update.body.clone(),
+    };
+
+    if let Some(update_state) = app.try_state::<UpdateState<R>>() {
+        let _ = update_state.tray_item.set_enabled(true);
+        let _ = update_state
+            .tray_item
+            .set_text(format!("Update available: {}", info.version));
+        *update_state.pending.lock().unwrap() = Some(update);
+    }
+
+    let _ = app
+        .notification()
+        .builder()
+        .title("Dex update available")
+        .body(format!("Version {} is ready to install.", info.version))
+        .show();
+
+    Ok(Some(info))
+}
+
+/// Download and install the update found by the last `check_for_update`
+/// call, emitting `update-download-progress` events as it goes.
+#[tauri::command]
+pub async fn install_update<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    let update_state = app
+        .try_state::<UpdateState<R>>()
+        .ok_or("updater is not initialized")?;
+    let update = update_state
+        .pending
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or("no update available; call check_for_update first")?;
+
+    let app_for_progress = app.clone();
+    let mut downloaded = 0u64;
+    update
+        .download_and_install(
+            move |chunk_length, content_length| {
+                downloaded += chunk_length as u64;
+                let _ = app_for_progress.emit(
+                    "update-download-progress",
+                    serde_json::json!({ "downloaded": downloaded, "total": content_length }),
+                );
+            },
+            || {
+                let _ = app.emit("update-download-finished", ());
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Set how often the background updater checks for a new release. Rejects
+/// anything below [`MIN_CHECK_INTERVAL_SECS`] rather than silently clamping,
+/// so the frontend can surface the actual floor to the user.
+#[tauri::command]
+pub async fn set_auto_check_interval<R: Runtime>(
+    app: AppHandle<R>,
+    interval_secs: u64,
+) -> Result<(), String> {
+    if interval_secs < MIN_CHECK_INTERVAL_SECS {
+        return Err(format!(
+            "auto-check interval must be at least {MIN_CHECK_INTERVAL_SECS} seconds"
+        ));
+    }
+    let mut state = load_state(&app)?;
+    state.auto_check_interval_secs = interval_secs;
+    save_state(&app, &state)
+}
+
+/// Spawn the background loop that periodically calls `check_for_update`,
+/// rate-limited by the persisted interval (and the persisted "last
+/// checked" timestamp, so a restart doesn't immediately re-check).
+pub fn spawn_background_checks<R: Runtime>(app: &AppHandle<R>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let state = load_state(&app).unwrap_or_default();
+            let elapsed = state
+                .last_checked_unix
+                .map(|last| now_unix().saturating_sub(last))
+                .unwrap_or(state.auto_check_interval_secs);
+
+            if elapsed < state.auto_check_interval_secs {
+                tokio::time::sleep(Duration::from_secs(
+                    state.auto_check_interval_secs - elapsed,
+                ))
+                .await;
+                continue;
+            }
+
+            let _ = check_for_update(app.clone()).await;
+            tokio::time::sleep(Duration::from_secs(state.auto_check_interval_secs)).await;
+        }
+    });
+}
@@ -0,0 +1,436 @@
+//! Named credential vault.
+//!
+//! Replaces the old single `(\"dex\", \"auth-key\")` keychain entry with a
+//! general `service`/`account` vault so the thin client can hold multiple
+//! identities, relay tokens and DWN signing keys side by side.
+//!
+//! Secrets are stored in the OS keychain when one is available. On hosts
+//! without a keychain (headless Linux with no Secret Service, or before the
+//! mobile secure-storage plugin is wired up) we fall back to an app-managed
+//! file encrypted with a passphrase-derived key. Callers can read
+//! [`VaultBackend`] off the result to know whether a passphrase prompt is
+//! required.
+
+use argon2::{Argon2, Params};
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Runtime};
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+use keyring::Entry;
+
+#[cfg(any(target_os = "android", target_os = "ios"))]
+use crate::mobile_secure_storage::SecureStorage;
+
+const SALT_FILE: &str = "vault.salt";
+const INDEX_FILE: &str = "vault.index.json";
+
+/// Argon2id params for deriving the encrypted-fallback key, well above the
+/// crate default (4 MiB / t=3): this path exists specifically to stand in
+/// for the OS keychain, so it shouldn't be meaningfully easier to brute
+/// force. 19 MiB / t=2 / p=1 matches current OWASP guidance for interactive
+/// login-style derivation.
+const ARGON2_MEMORY_KIB: u32 = 19 * 1024;
+const ARGON2_ITERATIONS: u32 = 2;
+const ARGON2_PARALLELISM: u32 = 1;
+
+/// Which backend actually served a vault request, so the frontend only
+/// prompts for a passphrase when the encrypted fallback is in play.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VaultBackend {
+    /// OS keychain (Secret Service, macOS Keychain, Windows Credential Manager).
+    Keychain,
+    /// App-managed file sealed with a passphrase-derived key.
+    EncryptedFile,
+}
+
+/// Result of a write, carrying the backend that was actually used.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultWriteResult {
+    pub backend: VaultBackend,
+}
+
+/// Result of a read. `backend` is populated whenever an entry is known to
+/// exist, even if `secret` comes back `None` because the caller hasn't
+/// supplied the passphrase the encrypted fallback needs yet — that's the
+/// frontend's cue to prompt before trying again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultReadResult {
+    pub secret: Option<String>,
+    pub backend: Option<VaultBackend>,
+}
+
+/// (service, account) -> backend, tracked so `vault_list_accounts` and
+/// `vault_backend_for` work the same way regardless of which backend a
+/// given secret ended up in.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct VaultIndex {
+    entries: Vec<(String, String, VaultBackend)>,
+}
+
+impl VaultIndex {
+    fn load(dir: &Path) -> Self {
+        fs::read(dir.join(INDEX_FILE))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, dir: &Path) -> Result<(), String> {
+        let bytes = serde_json::to_vec(self).map_err(|e| e.to_string())?;
+        fs::write(dir.join(INDEX_FILE), bytes).map_err(|e| e.to_string())
+    }
+
+    fn insert(&mut self, service: &str, account: &str, backend: VaultBackend) {
+        self.entries.retain(|(s, a, _)| !(s == service && a == account));
+        self.entries
+            .push((service.to_string(), account.to_string(), backend));
+    }
+
+    fn remove(&mut self, service: &str, account: &str) {
+        self.entries
+            .retain(|(s, a, _)| !(s == service && a == account));
+    }
+
+    fn accounts_for(&self, service: &str) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|(s, _, _)| s == service)
+            .map(|(_, a, _)| a.clone())
+            .collect()
+    }
+
+    fn backend_for(&self, service: &str, account: &str) -> Option<VaultBackend> {
+        self.entries
+            .iter()
+            .find(|(s, a, _)| s == service && a == account)
+            .map(|(_, _, backend)| *backend)
+    }
+}
+
+fn vault_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("vault");
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+/// `service`/`account` come straight from the frontend (that's the whole
+/// point of a named vault), so they can't be trusted as path components —
+/// percent-escape anything that isn't a plain filename character (notably
+/// `/`, `.` and `%` itself) rather than joining the raw strings onto `dir`.
+/// `_` is escaped too, even though it's filesystem-safe on its own, because
+/// `entry_path` uses a literal `__` to join the two sanitized segments —
+/// leaving `_` unescaped would let an adversarial `service`/`account` forge
+/// that separator and collide with a different pair. Escaping (vs.
+/// collapsing to a placeholder) keeps the mapping injective, so two
+/// different `service`/`account` pairs can never collide on disk.
+fn sanitize_path_segment(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' => out.push(byte as char),
+            _ => out.push_str(&format!("%{byte:02x}")),
+        }
+    }
+    out
+}
+
+fn entry_path(dir: &Path, service: &str, account: &str) -> PathBuf {
+    dir.join(format!(
+        "{}__{}.enc",
+        sanitize_path_segment(service),
+        sanitize_path_segment(account)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_path_does_not_collide_across_adversarial_pairs() {
+        let dir = Path::new("/tmp/dex-vault-test");
+        let pairs = [
+            ("foo_", "_bar"),
+            ("foo", "__bar"),
+            ("foo", "bar"),
+            ("foo_bar", ""),
+            ("", "foo_bar"),
+            ("a", "b c"),
+            ("a b", "c"),
+            ("a/../b", "c"),
+            ("a%b", "c"),
+        ];
+
+        let mut seen = std::collections::HashSet::new();
+        for (service, account) in pairs {
+            let path = entry_path(dir, service, account);
+            assert!(
+                seen.insert(path.clone()),
+                "collision for ({service:?}, {account:?}): {path:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn encrypted_write_read_roundtrip() {
+        let dir = std::env::temp_dir().join(format!(
+            "dex-vault-test-{:?}",
+            std::thread::current().id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+
+        encrypted_write(&dir, "relay", "alice", "top-secret", "correct horse").unwrap();
+        let secret = encrypted_read(&dir, "relay", "alice", "correct horse").unwrap();
+        assert_eq!(secret.as_deref(), Some("top-secret"));
+
+        let wrong_passphrase = encrypted_read(&dir, "relay", "alice", "wrong horse");
+        assert!(wrong_passphrase.is_err());
+
+        let missing = encrypted_read(&dir, "relay", "bob", "correct horse").unwrap();
+        assert_eq!(missing, None);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
+
+fn derive_key(dir: &Path, passphrase: &str) -> Result<Key, String> {
+    let salt_path = dir.join(SALT_FILE);
+    let salt = match fs::read(&salt_path) {
+        Ok(bytes) if bytes.len() == 16 => bytes,
+        _ => {
+            let mut salt = [0u8; 16];
+            use chacha20poly1305::aead::rand_core::RngCore;
+            OsRng.fill_bytes(&mut salt);
+            fs::write(&salt_path, salt).map_err(|e| e.to_string())?;
+            salt.to_vec()
+        }
+    };
+
+    let params = Params::new(
+        ARGON2_MEMORY_KIB,
+        ARGON2_ITERATIONS,
+        ARGON2_PARALLELISM,
+        Some(32),
+    )
+    .map_err(|e| e.to_string())?;
+    let argon2 = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+
+    let mut key_bytes = [0u8; 32];
+    argon2
+        .hash_password_into(passphrase.as_bytes(), &salt, &mut key_bytes)
+        .map_err(|e| e.to_string())?;
+    Ok(*Key::from_slice(&key_bytes))
+}
+
+fn encrypted_write(
+    dir: &Path,
+    service: &str,
+    account: &str,
+    secret: &str,
+    passphrase: &str,
+) -> Result<(), String> {
+    let key = derive_key(dir, passphrase)?;
+    let cipher = ChaCha20Poly1305::new(&key);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, secret.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    let mut blob = Vec::with_capacity(nonce.len() + ciphertext.len());
+    blob.extend_from_slice(&nonce);
+    blob.extend_from_slice(&ciphertext);
+    fs::write(entry_path(dir, service, account), blob).map_err(|e| e.to_string())
+}
+
+fn encrypted_read(
+    dir: &Path,
+    service: &str,
+    account: &str,
+    passphrase: &str,
+) -> Result<Option<String>, String> {
+    let path = entry_path(dir, service, account);
+    let blob = match fs::read(&path) {
+        Ok(blob) => blob,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.to_string()),
+    };
+    if blob.len() < 12 {
+        return Err("corrupt vault entry".to_string());
+    }
+    let (nonce, ciphertext) = blob.split_at(12);
+
+    let key = derive_key(dir, passphrase)?;
+    let cipher = ChaCha20Poly1305::new(&key);
+    let plaintext = cipher
+        .decrypt(nonce.into(), ciphertext)
+        .map_err(|_| "failed to decrypt vault entry (wrong passphrase?)".to_string())?;
+    String::from_utf8(plaintext).map_err(|e| e.to_string()).map(Some)
+}
+
+/// Store `secret` under `service`/`account`, preferring the OS keychain and
+/// falling back to the passphrase-encrypted file store when no keychain is
+/// available.
+#[tauri::command]
+pub async fn vault_set<R: Runtime>(
+    app: AppHandle<R>,
+    service: String,
+    account: String,
+    secret: String,
+    passphrase: Option<String>,
+) -> Result<VaultWriteResult, String> {
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    {
+        if let Ok(entry) = Entry::new(&service, &account) {
+            if entry.set_password(&secret).is_ok() {
+                let dir = vault_dir(&app)?;
+                let mut index = VaultIndex::load(&dir);
+                index.insert(&service, &account, VaultBackend::Keychain);
+                index.save(&dir)?;
+                return Ok(VaultWriteResult {
+                    backend: VaultBackend::Keychain,
+                });
+            }
+        }
+    }
+
+    #[cfg(any(target_os = "android", target_os = "ios"))]
+    {
+        if let Some(storage) = app.try_state::<SecureStorage<R>>() {
+            if storage.set(&service, &account, &secret).is_ok() {
+                let dir = vault_dir(&app)?;
+                let mut index = VaultIndex::load(&dir);
+                index.insert(&service, &account, VaultBackend::Keychain);
+                index.save(&dir)?;
+                return Ok(VaultWriteResult {
+                    backend: VaultBackend::Keychain,
+                });
+            }
+        }
+    }
+
+    let dir = vault_dir(&app)?;
+    let passphrase = passphrase.ok_or("a passphrase is required for the encrypted fallback")?;
+    encrypted_write(&dir, &service, &account, &secret, &passphrase)?;
+    let mut index = VaultIndex::load(&dir);
+    index.insert(&service, &account, VaultBackend::EncryptedFile);
+    index.save(&dir)?;
+    Ok(VaultWriteResult {
+        backend: VaultBackend::EncryptedFile,
+    })
+}
+
+/// Fetch the secret stored under `service`/`account`. The result always
+/// carries which backend the entry lives in (if any) — the frontend should
+/// check this *before* assuming a `None` secret means nothing was ever
+/// stored, since it may just mean the encrypted fallback needs a passphrase.
+#[tauri::command]
+pub async fn vault_get<R: Runtime>(
+    app: AppHandle<R>,
+    service: String,
+    account: String,
+    passphrase: Option<String>,
+) -> Result<VaultReadResult, String> {
+    let dir = vault_dir(&app)?;
+    let Some(backend) = VaultIndex::load(&dir).backend_for(&service, &account) else {
+        return Ok(VaultReadResult {
+            secret: None,
+            backend: None,
+        });
+    };
+
+    let secret = match backend {
+        VaultBackend::Keychain => {
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            {
+                Entry::new(&service, &account)
+                    .ok()
+                    .and_then(|entry| entry.get_password().ok())
+            }
+            #[cfg(any(target_os = "android", target_os = "ios"))]
+            {
+                app.try_state::<SecureStorage<R>>()
+                    .and_then(|storage| storage.get(&service, &account).ok().flatten())
+            }
+        }
+        VaultBackend::EncryptedFile => match passphrase {
+            Some(passphrase) => encrypted_read(&dir, &service, &account, &passphrase)?,
+            None => None,
+        },
+    };
+
+    Ok(VaultReadResult {
+        secret,
+        backend: Some(backend),
+    })
+}
+
+/// Look up which backend holds `service`/`account` without attempting to
+/// read the secret itself, so the frontend can decide to prompt for a
+/// passphrase *before* calling `vault_get`.
+#[tauri::command]
+pub async fn vault_backend_for<R: Runtime>(
+    app: AppHandle<R>,
+    service: String,
+    account: String,
+) -> Result<Option<VaultBackend>, String> {
+    let dir = vault_dir(&app)?;
+    Ok(VaultIndex::load(&dir).backend_for(&service, &account))
+}
+
+/// Delete the secret stored under `service`/`account` from whichever backend
+/// holds it.
+#[tauri::command]
+pub async fn vault_delete<R: Runtime>(
+    app: AppHandle<R>,
+    service: String,
+    account: String,
+) -> Result<(), String> {
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    {
+        if let Ok(entry) = Entry::new(&service, &account) {
+            match entry.delete_credential() {
+                Ok(()) | Err(keyring::Error::NoEntry) => {}
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+    }
+
+    #[cfg(any(target_os = "android", target_os = "ios"))]
+    {
+        if let Some(storage) = app.try_state::<SecureStorage<R>>() {
+            let _ = storage.delete(&service, &account);
+        }
+    }
+
+    let dir = vault_dir(&app)?;
+    let path = entry_path(&dir, &service, &account);
+    if path.exists() {
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+
+    let mut index = VaultIndex::load(&dir);
+    index.remove(&service, &account);
+    index.save(&dir)
+}
+
+/// List the accounts stored for a given service, independent of which
+/// backend each one lives in.
+#[tauri::command]
+pub async fn vault_list_accounts<R: Runtime>(
+    app: AppHandle<R>,
+    service: String,
+) -> Result<Vec<String>, String> {
+    let dir = vault_dir(&app)?;
+    Ok(VaultIndex::load(&dir).accounts_for(&service))
+}